@@ -6,17 +6,24 @@ use crate::check::{
     self, CheckResult, Collection, LevelsChecker, LevelsCheckerArgs, OutputType, Real,
     SimpleCheckResult,
 };
+use sha1::{Digest, Sha1};
 use std::collections::HashSet;
 use std::convert::AsRef;
 use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::net::ToSocketAddrs;
+use std::time::Duration as StdDuration;
 use time::Duration;
 use typed_builder::TypedBuilder;
 use x509_parser::certificate::{BasicExtension, Validity, X509Certificate};
 use x509_parser::error::X509Error;
-use x509_parser::extensions::{GeneralName, SubjectAlternativeName};
+use x509_parser::extensions::{
+    AuthorityInfoAccess, BasicConstraints, CRLDistributionPoints, DistributionPointName,
+    ExtendedKeyUsage, GeneralName, KeyUsage, SubjectAlternativeName,
+};
 use x509_parser::prelude::FromDer;
 use x509_parser::prelude::{oid2sn, oid_registry, AlgorithmIdentifier};
 use x509_parser::public_key::PublicKey;
+use x509_parser::revocation_list::CertificateRevocationList;
 use x509_parser::signature_algorithm::SignatureAlgorithm as X509SignatureAlgorithm;
 use x509_parser::time::ASN1Time;
 use x509_parser::x509::{AttributeTypeAndValue, SubjectPublicKeyInfo};
@@ -77,6 +84,64 @@ impl Display for SignatureAlgorithm {
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevocationMode {
+    Off,
+    Crl,
+    Ocsp,
+    PreferOcsp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyUsageBit {
+    DigitalSignature,
+    NonRepudiation,
+    KeyEncipherment,
+    DataEncipherment,
+    KeyAgreement,
+    KeyCertSign,
+    CrlSign,
+    EncipherOnly,
+    DecipherOnly,
+}
+
+impl Display for KeyUsageBit {
+    fn fmt(&self, f: &mut Formatter) -> FormatResult {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::DigitalSignature => "digitalSignature",
+                Self::NonRepudiation => "nonRepudiation",
+                Self::KeyEncipherment => "keyEncipherment",
+                Self::DataEncipherment => "dataEncipherment",
+                Self::KeyAgreement => "keyAgreement",
+                Self::KeyCertSign => "keyCertSign",
+                Self::CrlSign => "cRLSign",
+                Self::EncipherOnly => "encipherOnly",
+                Self::DecipherOnly => "decipherOnly",
+            }
+        )
+    }
+}
+
+impl KeyUsageBit {
+    fn is_set(&self, key_usage: &KeyUsage) -> bool {
+        match self {
+            Self::DigitalSignature => key_usage.digital_signature(),
+            Self::NonRepudiation => key_usage.non_repudiation(),
+            Self::KeyEncipherment => key_usage.key_encipherment(),
+            Self::DataEncipherment => key_usage.data_encipherment(),
+            Self::KeyAgreement => key_usage.key_agreement(),
+            Self::KeyCertSign => key_usage.key_cert_sign(),
+            Self::CrlSign => key_usage.crl_sign(),
+            Self::EncipherOnly => key_usage.encipher_only(),
+            Self::DecipherOnly => key_usage.decipher_only(),
+        }
+    }
+}
+
 #[derive(Debug, TypedBuilder)]
 #[builder(field_defaults(default))]
 pub struct Config {
@@ -95,13 +160,216 @@ pub struct Config {
     issuer_c: Option<String>,
     not_after: Option<LevelsChecker<Duration>>,
     max_validity: Option<Duration>,
+    trust_anchors: Option<Vec<Vec<u8>>>,
+    revocation: Option<RevocationMode>,
+    key_usage: Option<Vec<KeyUsageBit>>,
+    ext_key_usage: Option<Vec<String>>,
+    is_ca: Option<bool>,
 }
 
-pub fn check(der: &[u8], config: Config) -> Collection {
-    let cert = match X509Certificate::from_der(der) {
-        Ok((_rem, cert)) => cert,
-        Err(_) => check::abort("Failed to parse certificate"),
+/// Protocol preamble to run before the TLS handshake, for servers that speak
+/// plaintext first and only switch to TLS once asked (RFC 2595/3207/2228).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartTls {
+    Smtp,
+    Imap,
+    Pop3,
+    Ftp,
+}
+
+/// Fetch the certificate chain a TLS endpoint actually presents and run it
+/// through [`check`], so operators can monitor the cert served by a host
+/// instead of supplying DER out-of-band.
+///
+/// The connection always goes to `host`; `sni`, if present, is used as the
+/// TLS server name for the handshake, and `host` is used otherwise. `timeout`
+/// bounds the TCP connect and each individual read and write of the
+/// STARTTLS preamble and the handshake, not the exchange as a whole. Any
+/// failure to fetch the chain, a timeout included, is a single CRIT.
+pub fn check_from_server(
+    host: &str,
+    port: u16,
+    sni: Option<&str>,
+    starttls: Option<StartTls>,
+    timeout: StdDuration,
+    config: Config,
+) -> Collection {
+    match fetch_peer_chain(host, port, sni, starttls, timeout) {
+        Ok(chain) => check(&chain, config),
+        Err(err) => Collection::from(&mut vec![SimpleCheckResult::crit(format!(
+            "Failed to fetch certificate from {}:{}: {}",
+            host, port, err
+        ))
+        .into()]),
+    }
+}
+
+fn fetch_peer_chain(
+    host: &str,
+    port: u16,
+    sni: Option<&str>,
+    starttls: Option<StartTls>,
+    timeout: StdDuration,
+) -> Result<Vec<Vec<u8>>, String> {
+    use std::net::TcpStream;
+
+    let addr = format!("{}:{}", host, port);
+    let mut tcp = TcpStream::connect_timeout(
+        &addr
+            .to_socket_addrs()
+            .map_err(|err| err.to_string())?
+            .next()
+            .ok_or_else(|| "could not resolve address".to_string())?,
+        timeout,
+    )
+    .map_err(|err| err.to_string())?;
+    tcp.set_read_timeout(Some(timeout))
+        .map_err(|err| err.to_string())?;
+    tcp.set_write_timeout(Some(timeout))
+        .map_err(|err| err.to_string())?;
+
+    if let Some(starttls) = starttls {
+        run_starttls_preamble(&mut tcp, starttls)?;
+    }
+
+    let server_name = sni.unwrap_or(host);
+    let mut tls_config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(std::sync::Arc::new(AcceptAnyCertVerifier))
+        .with_no_client_auth();
+    tls_config.enable_sni = true;
+
+    let server_name = rustls::ServerName::try_from(server_name)
+        .map_err(|_| format!("\"{}\" is not a valid DNS name for SNI", server_name))?;
+    let mut conn = rustls::ClientConnection::new(std::sync::Arc::new(tls_config), server_name)
+        .map_err(|err| err.to_string())?;
+
+    // We only need the peer's certificate chain, not to exchange
+    // application data, so there's no read/write traffic to piggyback the
+    // handshake on -- drive it to completion ourselves.
+    while conn.is_handshaking() {
+        conn.complete_io(&mut tcp).map_err(|err| err.to_string())?;
+    }
+
+    conn.peer_certificates()
+        .map(|certs| certs.iter().map(|cert| cert.0.clone()).collect())
+        .ok_or_else(|| "server did not present a certificate".to_string())
+}
+
+/// Speak just enough of each protocol's plaintext preamble to reach the
+/// point where the server expects a `STARTTLS`/`STLS`/`AUTH TLS` upgrade.
+fn run_starttls_preamble(
+    stream: &mut std::net::TcpStream,
+    starttls: StartTls,
+) -> Result<(), String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut reader = BufReader::new(stream.try_clone().map_err(|err| err.to_string())?);
+    let mut line = String::new();
+
+    match starttls {
+        StartTls::Smtp => {
+            reader.read_line(&mut line).map_err(|err| err.to_string())?; // greeting
+            stream
+                .write_all(b"EHLO checkmk\r\n")
+                .map_err(|err| err.to_string())?;
+            drain_until_done(&mut reader, "250 ")?;
+            stream
+                .write_all(b"STARTTLS\r\n")
+                .map_err(|err| err.to_string())?;
+            line.clear();
+            reader.read_line(&mut line).map_err(|err| err.to_string())?;
+            if !line.starts_with("220") {
+                return Err(format!("server refused STARTTLS: {}", line.trim_end()));
+            }
+        }
+        StartTls::Imap => {
+            reader.read_line(&mut line).map_err(|err| err.to_string())?; // greeting
+            stream
+                .write_all(b"a1 STARTTLS\r\n")
+                .map_err(|err| err.to_string())?;
+            line.clear();
+            reader.read_line(&mut line).map_err(|err| err.to_string())?;
+            if !line.starts_with("a1 OK") {
+                return Err(format!("server refused STARTTLS: {}", line.trim_end()));
+            }
+        }
+        StartTls::Pop3 => {
+            reader.read_line(&mut line).map_err(|err| err.to_string())?; // greeting
+            stream
+                .write_all(b"STLS\r\n")
+                .map_err(|err| err.to_string())?;
+            line.clear();
+            reader.read_line(&mut line).map_err(|err| err.to_string())?;
+            if !line.starts_with("+OK") {
+                return Err(format!("server refused STLS: {}", line.trim_end()));
+            }
+        }
+        StartTls::Ftp => {
+            reader.read_line(&mut line).map_err(|err| err.to_string())?; // greeting
+            stream
+                .write_all(b"AUTH TLS\r\n")
+                .map_err(|err| err.to_string())?;
+            line.clear();
+            reader.read_line(&mut line).map_err(|err| err.to_string())?;
+            if !line.starts_with("234") {
+                return Err(format!("server refused AUTH TLS: {}", line.trim_end()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn drain_until_done(reader: &mut impl std::io::BufRead, last_line_prefix: &str) -> Result<(), String> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|err| err.to_string())?;
+        if line.starts_with(last_line_prefix) && line.as_bytes().get(3) == Some(&b' ') {
+            return Ok(());
+        }
+        if line.is_empty() {
+            return Err("connection closed during STARTTLS preamble".to_string());
+        }
+    }
+}
+
+/// We only use the TLS handshake to harvest the chain the server presents;
+/// [`check`] (and in particular `trust_anchors`) performs the real
+/// verification, so this verifier accepts anything.
+#[derive(Debug)]
+struct AcceptAnyCertVerifier;
+
+impl rustls::client::ServerCertVerifier for AcceptAnyCertVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+/// Check a certificate chain.
+///
+/// `chain` must contain the leaf certificate first, followed by zero or more
+/// intermediates in issuance order (leaf -> ... -> closest to the root).
+/// Fields on `config` that target "the certificate" (subject, issuer, key
+/// usage, ...) are evaluated against the leaf, i.e. `chain[0]`.
+pub fn check(chain: &[Vec<u8>], config: Config) -> Collection {
+    let certs = match chain
+        .iter()
+        .map(|der| X509Certificate::from_der(der).map(|(_rem, cert)| cert))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(certs) if !certs.is_empty() => certs,
+        _ => check::abort("Failed to parse certificate"),
     };
+    let cert = &certs[0];
 
     Collection::from(&mut unwrap_into!(
         config.subject_cn.map(|expected| {
@@ -179,6 +447,11 @@ pub fn check(der: &[u8], config: Config) -> Collection {
         )
         .map(|cr: CheckResult<Duration>| cr.map(|x| Real::from(x.whole_days() as isize))),
         check_max_validity(cert.validity(), config.max_validity),
+        check_chain_of_trust(&certs, config.trust_anchors),
+        check_revocation(&certs, config.revocation),
+        check_key_usage(cert.key_usage(), config.key_usage),
+        check_ext_key_usage(cert.extended_key_usage(), config.ext_key_usage),
+        check_is_ca(cert.basic_constraints(), config.is_ca),
     ))
 }
 
@@ -300,6 +573,89 @@ fn check_pubkey_size(
     })
 }
 
+fn check_key_usage(
+    key_usage: Result<Option<BasicExtension<&KeyUsage>>, X509Error>,
+    expected: Option<Vec<KeyUsageBit>>,
+) -> Option<SimpleCheckResult> {
+    expected.map(|expected| match key_usage {
+        Err(err) => SimpleCheckResult::crit(format!("Key usage: {}", err)),
+        Ok(None) => SimpleCheckResult::warn("No key usage extension present"),
+        Ok(Some(ext)) => {
+            let missing: Vec<String> = expected
+                .iter()
+                .filter(|bit| !bit.is_set(ext.value))
+                .map(|bit| bit.to_string())
+                .collect();
+            if missing.is_empty() {
+                SimpleCheckResult::notice("Key usage as expected")
+            } else {
+                SimpleCheckResult::warn(format!("Key usage: missing {}", missing.join(", ")))
+            }
+        }
+    })
+}
+
+fn check_ext_key_usage(
+    ext_key_usage: Result<
+        Option<BasicExtension<&ExtendedKeyUsage>>,
+        X509Error,
+    >,
+    expected: Option<Vec<String>>,
+) -> Option<SimpleCheckResult> {
+    expected.map(|expected| match ext_key_usage {
+        Err(err) => SimpleCheckResult::crit(format!("Extended key usage: {}", err)),
+        Ok(None) => SimpleCheckResult::warn("No extended key usage extension present"),
+        Ok(Some(ext)) => {
+            let found: HashSet<&str> = HashSet::from_iter(
+                [
+                    ext.value.any.then_some("anyExtendedKeyUsage"),
+                    ext.value.server_auth.then_some("serverAuth"),
+                    ext.value.client_auth.then_some("clientAuth"),
+                    ext.value.code_signing.then_some("codeSigning"),
+                    ext.value.email_protection.then_some("emailProtection"),
+                    ext.value.time_stamping.then_some("timeStamping"),
+                    ext.value.ocsp_signing.then_some("OCSPSigning"),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+            // RFC 5280 4.2.1.12: a cert asserting anyExtendedKeyUsage is valid
+            // for every purpose, so it satisfies any EKU we were asked to check.
+            let missing: Vec<&str> = if ext.value.any {
+                Vec::new()
+            } else {
+                expected
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .filter(|eku| !found.contains(eku))
+                    .collect()
+            };
+            if missing.is_empty() {
+                SimpleCheckResult::notice("Extended key usage as expected")
+            } else {
+                SimpleCheckResult::warn(format!(
+                    "Extended key usage: missing {}",
+                    missing.join(", ")
+                ))
+            }
+        }
+    })
+}
+
+fn check_is_ca(
+    basic_constraints: Result<
+        Option<BasicExtension<&BasicConstraints>>,
+        X509Error,
+    >,
+    expected: Option<bool>,
+) -> Option<SimpleCheckResult> {
+    expected.map(|expected| match basic_constraints {
+        Err(err) => SimpleCheckResult::crit(format!("Basic constraints: {}", err)),
+        Ok(None) => check_eq!("Is CA", false, expected),
+        Ok(Some(ext)) => check_eq!("Is CA", ext.value.ca, expected),
+    })
+}
+
 fn check_validity_not_after(
     time_to_expiration: Option<Duration>,
     levels: Option<LevelsChecker<Duration>>,
@@ -342,6 +698,502 @@ fn check_max_validity(
     })
 }
 
+/// If `trust_anchors` is configured, verify `chain` (leaf first) link by link
+/// and confirm the chain can be completed up to one of them. Without trust
+/// anchors nothing is checked and `None` is returned.
+///
+/// For every parent/child pair this checks that the issuer DN of the child
+/// matches the subject DN of the parent, that the parent's signature over the
+/// child actually verifies, that the parent is marked as a CA, and that any
+/// `pathLenConstraint` the parent carries is not exceeded by the number of
+/// intermediates below it. Every certificate in the chain is also required to
+/// be temporally valid.
+fn check_chain_of_trust(
+    certs: &[X509Certificate],
+    trust_anchors: Option<Vec<Vec<u8>>>,
+) -> Option<SimpleCheckResult> {
+    trust_anchors.map(|anchors| {
+        let anchors = match anchors
+            .iter()
+            .map(|der| X509Certificate::from_der(der).map(|(_rem, cert)| cert))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(anchors) => anchors,
+            Err(err) => {
+                return SimpleCheckResult::crit(format!("Failed to parse trust anchor: {}", err))
+            }
+        };
+
+        for (depth, cert) in certs.iter().enumerate() {
+            if cert.validity().time_to_expiration().is_none() {
+                return SimpleCheckResult::crit(format!(
+                    "Certificate at depth {} is not temporally valid ({})",
+                    depth,
+                    cert.validity().not_after
+                ));
+            }
+        }
+
+        for (depth, pair) in certs.windows(2).enumerate() {
+            let (child, parent) = (&pair[0], &pair[1]);
+            if child.issuer() != parent.subject() {
+                return SimpleCheckResult::crit(format!(
+                    "Chain broken at depth {}: issuer \"{}\" does not match subject \"{}\"",
+                    depth,
+                    child.issuer(),
+                    parent.subject()
+                ));
+            }
+            if let Err(err) = verify_issued_by(child, parent, depth) {
+                return SimpleCheckResult::crit(err);
+            }
+        }
+
+        let last = certs.last().expect("certs is non-empty");
+        match anchors.iter().find(|anchor| anchor.subject() == last.issuer()) {
+            Some(anchor) if anchor.subject() == anchor.issuer() => {
+                match verify_issued_by(last, anchor, certs.len() - 1) {
+                    Ok(()) => SimpleCheckResult::notice(format!(
+                        "Certificate chain verified up to trust anchor \"{}\"",
+                        anchor.subject()
+                    )),
+                    Err(err) => SimpleCheckResult::crit(err),
+                }
+            }
+            Some(_) => SimpleCheckResult::crit("Root of chain is untrusted: not self-signed"),
+            None => SimpleCheckResult::crit(
+                "Certificate chain cannot be completed up to a configured trust anchor",
+            ),
+        }
+    })
+}
+
+/// Verify that `parent` actually issued `child`: the signature over `child`
+/// must verify against `parent`'s public key, `parent` must be a CA, and any
+/// `pathLenConstraint` it carries must not be exceeded by the number of
+/// intermediates below it (`depth`, 0 for the leaf's immediate issuer).
+fn verify_issued_by(
+    child: &X509Certificate,
+    parent: &X509Certificate,
+    depth: usize,
+) -> Result<(), String> {
+    child
+        .verify_signature(Some(parent.public_key()))
+        .map_err(|err| format!("Signature verification failed at depth {}: {}", depth, err))?;
+
+    match parent.basic_constraints() {
+        Ok(Some(bc)) if bc.value.ca => {
+            if let Some(path_len) = bc.value.path_len_constraint {
+                if (depth as u32) > path_len {
+                    return Err(format!(
+                        "pathLenConstraint of {} exceeded at depth {}",
+                        path_len, depth
+                    ));
+                }
+            }
+            Ok(())
+        }
+        Ok(_) => Err(format!(
+            "Issuer \"{}\" is not marked as a CA",
+            parent.subject()
+        )),
+        Err(err) => Err(format!("Failed to parse BasicConstraints: {}", err)),
+    }
+}
+
+/// Check the leaf certificate (`certs[0]`) against its issuer (`certs[1]`)
+/// for revocation, per the configured `RevocationMode`.
+///
+/// `PreferOcsp` tries OCSP first and only falls back to CRL checking when no
+/// OCSP responder is advertised, the responder could not be reached, or its
+/// response could not be parsed or had a non-successful status.
+fn check_revocation(
+    certs: &[X509Certificate],
+    mode: Option<RevocationMode>,
+) -> Option<SimpleCheckResult> {
+    mode.and_then(|mode| {
+        if mode == RevocationMode::Off {
+            return None;
+        }
+        let leaf = &certs[0];
+        let issuer = match certs.get(1) {
+            Some(issuer) => issuer,
+            None => {
+                return Some(SimpleCheckResult::warn(
+                    "Revocation check skipped: issuer certificate not available",
+                ))
+            }
+        };
+
+        Some(match mode {
+            RevocationMode::Off => unreachable!(),
+            RevocationMode::Crl => check_revocation_via_crl(leaf, issuer).into_result(),
+            RevocationMode::Ocsp => check_revocation_via_ocsp(leaf, issuer).into_result(),
+            RevocationMode::PreferOcsp => match check_revocation_via_ocsp(leaf, issuer) {
+                RevocationOutcome::Unavailable(_) => check_revocation_via_crl(leaf, issuer).into_result(),
+                determined => determined.into_result(),
+            },
+        })
+    })
+}
+
+/// Outcome of a single revocation check: either a determined verdict, or a
+/// reason the determination could not be made (fetch timeout, missing
+/// extension, ...), which `PreferOcsp` uses to decide whether to fall back.
+enum RevocationOutcome {
+    Determined(SimpleCheckResult),
+    Unavailable(String),
+}
+
+impl RevocationOutcome {
+    fn into_result(self) -> SimpleCheckResult {
+        match self {
+            Self::Determined(result) => result,
+            Self::Unavailable(reason) => SimpleCheckResult::warn(format!(
+                "Revocation unavailable: {}",
+                reason
+            )),
+        }
+    }
+}
+
+const CRL_DISTRIBUTION_POINTS_OID: &str = "2.5.29.31";
+const AUTHORITY_INFO_ACCESS_OID: &str = "1.3.6.1.5.5.7.1.1";
+const OCSP_ACCESS_METHOD_OID: &str = "1.3.6.1.5.5.7.48.1";
+
+fn check_revocation_via_crl(leaf: &X509Certificate, issuer: &X509Certificate) -> RevocationOutcome {
+    let url = match crl_distribution_point_url(leaf) {
+        Some(url) => url,
+        None => return RevocationOutcome::Unavailable("no CRL distribution point".to_string()),
+    };
+
+    let bytes = match http_get(&url) {
+        Ok(bytes) => bytes,
+        Err(err) => return RevocationOutcome::Unavailable(err),
+    };
+
+    let crl = match CertificateRevocationList::from_der(&bytes) {
+        Ok((_rem, crl)) => crl,
+        Err(err) => {
+            return RevocationOutcome::Determined(SimpleCheckResult::crit(format!(
+                "Failed to parse CRL: {}",
+                err
+            )))
+        }
+    };
+
+    if let Err(err) = crl.verify_signature(issuer.public_key()) {
+        return RevocationOutcome::Determined(SimpleCheckResult::crit(format!(
+            "CRL signature verification failed: {}",
+            err
+        )));
+    }
+
+    let serial = leaf.raw_serial_as_string();
+    RevocationOutcome::Determined(
+        match crl
+            .iter_revoked_certificates()
+            .find(|entry| entry.raw_serial_as_string() == serial)
+        {
+            Some(entry) => {
+                let reason = entry
+                    .reason_code()
+                    .map(|(_, reason)| format!("{:?}", reason.0))
+                    .unwrap_or_else(|| "unspecified".to_string());
+                SimpleCheckResult::crit(format!(
+                    "Certificate revoked on {} (reason: {})",
+                    entry.revocation_date, reason
+                ))
+            }
+            None => SimpleCheckResult::notice("Certificate not revoked (CRL)"),
+        },
+    )
+}
+
+fn check_revocation_via_ocsp(
+    leaf: &X509Certificate,
+    issuer: &X509Certificate,
+) -> RevocationOutcome {
+    let url = match ocsp_responder_url(leaf) {
+        Some(url) => url,
+        None => return RevocationOutcome::Unavailable("no OCSP responder".to_string()),
+    };
+
+    let request = build_ocsp_request(leaf, issuer);
+    let response = match http_post_ocsp(&url, &request) {
+        Ok(bytes) => bytes,
+        Err(err) => return RevocationOutcome::Unavailable(err),
+    };
+
+    match parse_ocsp_cert_status(&response) {
+        Ok(OcspCertStatus::Good) => {
+            RevocationOutcome::Determined(SimpleCheckResult::notice("Certificate not revoked (OCSP)"))
+        }
+        Ok(OcspCertStatus::Revoked) => {
+            RevocationOutcome::Determined(SimpleCheckResult::crit("Certificate revoked (OCSP)"))
+        }
+        Ok(OcspCertStatus::Unknown) => RevocationOutcome::Determined(SimpleCheckResult::warn(
+            "OCSP responder does not know this certificate",
+        )),
+        Err(err) => RevocationOutcome::Unavailable(err),
+    }
+}
+
+fn crl_distribution_point_url(cert: &X509Certificate) -> Option<String> {
+    let ext = cert
+        .extensions()
+        .iter()
+        .find(|ext| ext.oid.to_id_string() == CRL_DISTRIBUTION_POINTS_OID)?;
+    let (_rem, points) = CRLDistributionPoints::from_der(ext.value).ok()?;
+    points.iter().find_map(|point| match &point.distribution_point {
+        Some(DistributionPointName::FullName(names)) => names.iter().find_map(|name| match name {
+            GeneralName::URI(uri) => Some(uri.to_string()),
+            _ => None,
+        }),
+        _ => None,
+    })
+}
+
+fn ocsp_responder_url(cert: &X509Certificate) -> Option<String> {
+    let ext = cert
+        .extensions()
+        .iter()
+        .find(|ext| ext.oid.to_id_string() == AUTHORITY_INFO_ACCESS_OID)?;
+    let (_rem, aia) = AuthorityInfoAccess::from_der(ext.value).ok()?;
+    aia.accessdescs.iter().find_map(|desc| {
+        if desc.access_method.to_id_string() != OCSP_ACCESS_METHOD_OID {
+            return None;
+        }
+        match desc.access_location {
+            GeneralName::URI(uri) => Some(uri.to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// Build a minimal RFC 6960 `OCSPRequest` for a single `CertID`, hashing the
+/// issuer's subject DN and public key bytes with SHA-1 as required by the
+/// default `id-sha1` hash algorithm. `optionalSignature` is omitted, as is
+/// everything optional in `TBSRequest` (`version`, `requestorName`,
+/// `requestExtensions`) -- requests are unsigned and carry a single `Request`.
+fn build_ocsp_request(leaf: &X509Certificate, issuer: &X509Certificate) -> Vec<u8> {
+    let issuer_name_hash = Sha1::digest(issuer.subject().as_raw());
+    let issuer_key_hash = Sha1::digest(issuer.public_key().subject_public_key.as_ref());
+    let serial = leaf.raw_serial();
+
+    let cert_id = der_sequence(&[
+        der_sequence(&[der_oid(&[1, 3, 14, 3, 2, 26]), der_null()]),
+        der_octet_string(&issuer_name_hash),
+        der_octet_string(&issuer_key_hash),
+        der_integer(serial),
+    ]);
+    let request = der_sequence(&[cert_id]);
+    let request_list = der_sequence(&[request]);
+    let tbs_request = der_sequence(&[request_list]);
+
+    der_sequence(&[tbs_request])
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum OcspCertStatus {
+    Good,
+    Revoked,
+    Unknown,
+}
+
+/// Parse just enough of an `OCSPResponse`/`BasicOCSPResponse` to recover the
+/// `CertStatus` of the first `SingleResponse` -- the one matching the
+/// `CertID` we asked about, since we only ever send a single request.
+///
+/// Full ASN.1 decoding of `BasicOCSPResponse` (producedAt, extensions,
+/// embedded responder certs, signature verification, ...) is out of scope
+/// here; callers that need the complete response should go through a
+/// dedicated OCSP client crate instead.
+fn parse_ocsp_cert_status(response: &[u8]) -> Result<OcspCertStatus, String> {
+    let (tag, ocsp_response, _) = der_read_tlv(response)?;
+    expect_der_tag(tag, 0x30, "OCSPResponse")?;
+
+    let (status_tag, response_status, after_status) = der_read_tlv(ocsp_response)?;
+    expect_der_tag(status_tag, 0x0a, "OCSPResponseStatus")?;
+    if response_status != [0x00] {
+        return Err(format!(
+            "OCSP responder returned non-successful status {:?}",
+            response_status
+        ));
+    }
+
+    let (bytes_tag, response_bytes, _) = der_read_tlv(after_status)?;
+    if bytes_tag != 0xa0 {
+        return Err("OCSP response has no responseBytes".to_string());
+    }
+    let (seq_tag, response_bytes, _) = der_read_tlv(response_bytes)?;
+    expect_der_tag(seq_tag, 0x30, "ResponseBytes")?;
+    let (_response_type_tag, _response_type, after_response_type) = der_read_tlv(response_bytes)?;
+    let (octet_tag, basic_response, _) = der_read_tlv(after_response_type)?;
+    expect_der_tag(octet_tag, 0x04, "BasicOCSPResponse OCTET STRING")?;
+
+    let (basic_tag, basic_response, _) = der_read_tlv(basic_response)?;
+    expect_der_tag(basic_tag, 0x30, "BasicOCSPResponse")?;
+    let (tbs_tag, response_data, _) = der_read_tlv(basic_response)?;
+    expect_der_tag(tbs_tag, 0x30, "ResponseData")?;
+
+    // ResponseData ::= SEQUENCE { version [0] EXPLICIT Version DEFAULT v1,
+    // responderID ResponderID, producedAt GeneralizedTime,
+    // responses SEQUENCE OF SingleResponse, ... }. `version` is an
+    // explicit, defaulted field, so skip over it only if present.
+    let (first_tag, _, after_first) = der_read_tlv(response_data)?;
+    let after_responder_id = if first_tag == 0xa0 {
+        der_read_tlv(after_first)?.2
+    } else {
+        after_first
+    };
+    let (_produced_at_tag, _, after_produced_at) = der_read_tlv(after_responder_id)?;
+
+    let (responses_tag, responses, _) = der_read_tlv(after_produced_at)?;
+    expect_der_tag(responses_tag, 0x30, "responses")?;
+    let (single_tag, single_response, _) = der_read_tlv(responses)?;
+    expect_der_tag(single_tag, 0x30, "SingleResponse")?;
+
+    // SingleResponse ::= SEQUENCE { certID CertID, certStatus CertStatus, ... }
+    let (_cert_id_tag, _, after_cert_id) = der_read_tlv(single_response)?;
+    let (cert_status_tag, _, _) = der_read_tlv(after_cert_id)?;
+
+    match cert_status_tag {
+        0x80 => Ok(OcspCertStatus::Good),
+        0xa1 => Ok(OcspCertStatus::Revoked),
+        0x82 => Ok(OcspCertStatus::Unknown),
+        other => Err(format!("unrecognized OCSP certStatus tag {:#04x}", other)),
+    }
+}
+
+fn http_get(url: &str) -> Result<Vec<u8>, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(StdDuration::from_secs(10))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let response = client.get(url).send().map_err(|err| err.to_string())?;
+    response
+        .bytes()
+        .map(|b| b.to_vec())
+        .map_err(|err| err.to_string())
+}
+
+fn http_post_ocsp(url: &str, request: &[u8]) -> Result<Vec<u8>, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(StdDuration::from_secs(10))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/ocsp-request")
+        .body(request.to_vec())
+        .send()
+        .map_err(|err| err.to_string())?;
+    response
+        .bytes()
+        .map(|b| b.to_vec())
+        .map_err(|err| err.to_string())
+}
+
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    if content.len() < 0x80 {
+        out.push(content.len() as u8);
+    } else {
+        let len_bytes = content.len().to_be_bytes();
+        let len_bytes = len_bytes
+            .iter()
+            .skip_while(|b| **b == 0)
+            .copied()
+            .collect::<Vec<u8>>();
+        out.push(0x80 | len_bytes.len() as u8);
+        out.extend(len_bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+fn der_sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    der_tlv(0x30, &items.concat())
+}
+
+fn der_octet_string(bytes: &[u8]) -> Vec<u8> {
+    der_tlv(0x04, bytes)
+}
+
+fn der_null() -> Vec<u8> {
+    der_tlv(0x05, &[])
+}
+
+fn der_oid(arcs: &[u64]) -> Vec<u8> {
+    let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+    for &arc in &arcs[2..] {
+        if arc < 0x80 {
+            content.push(arc as u8);
+        } else {
+            let mut bytes = Vec::new();
+            let mut value = arc;
+            while value > 0 {
+                bytes.push((value & 0x7f) as u8);
+                value >>= 7;
+            }
+            bytes.reverse();
+            let last = bytes.len() - 1;
+            for b in &mut bytes[..last] {
+                *b |= 0x80;
+            }
+            content.extend(bytes);
+        }
+    }
+    der_tlv(0x06, &content)
+}
+
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+    if bytes.is_empty() {
+        return der_tlv(0x02, &[0]);
+    }
+    if bytes[0] & 0x80 != 0 {
+        let mut content = vec![0];
+        content.extend_from_slice(bytes);
+        der_tlv(0x02, &content)
+    } else {
+        der_tlv(0x02, bytes)
+    }
+}
+
+/// Read one definite-length DER TLV off the front of `data`, returning its
+/// tag, content, and the remaining bytes. Indefinite-length encoding isn't
+/// valid DER and isn't supported.
+fn der_read_tlv(data: &[u8]) -> Result<(u8, &[u8], &[u8]), String> {
+    let &tag = data.first().ok_or("truncated DER: missing tag")?;
+    let &first_len = data.get(1).ok_or("truncated DER: missing length")?;
+    let (len, content_start) = if first_len & 0x80 == 0 {
+        (first_len as usize, 2)
+    } else {
+        let n = (first_len & 0x7f) as usize;
+        let len_bytes = data
+            .get(2..2 + n)
+            .ok_or("truncated DER: missing length bytes")?;
+        let len = len_bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        (len, 2 + n)
+    };
+    let content = data
+        .get(content_start..content_start + len)
+        .ok_or("truncated DER: content shorter than declared length")?;
+    Ok((tag, content, &data[content_start + len..]))
+}
+
+fn expect_der_tag(tag: u8, expected: u8, what: &str) -> Result<(), String> {
+    if tag == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}: expected DER tag {:#04x}, got {:#04x}",
+            what, expected, tag
+        ))
+    }
+}
+
 #[cfg(test)]
 mod test_check_serial {
     use super::{check_serial, SimpleCheckResult};
@@ -372,3 +1224,630 @@ mod test_check_serial {
         );
     }
 }
+
+#[cfg(test)]
+mod test_der_helpers {
+    use super::{der_integer, der_null, der_oid, der_read_tlv, der_sequence, der_tlv, expect_der_tag};
+
+    #[test]
+    fn test_tlv_short_and_long_form_length() {
+        assert_eq!(der_tlv(0x04, &[1, 2, 3]), vec![0x04, 0x03, 1, 2, 3]);
+
+        let long_content = vec![0xab; 200];
+        let encoded = der_tlv(0x04, &long_content);
+        assert_eq!(&encoded[..3], &[0x04, 0x81, 200]);
+        assert_eq!(&encoded[3..], long_content.as_slice());
+    }
+
+    #[test]
+    fn test_sequence_nests_and_concatenates_items() {
+        let seq = der_sequence(&[vec![0x01, 0x00], vec![0x02, 0x01, 0x05]]);
+        assert_eq!(seq, vec![0x30, 0x05, 0x01, 0x00, 0x02, 0x01, 0x05]);
+    }
+
+    #[test]
+    fn test_oid_multi_byte_arc() {
+        // id-sha1: 1.3.14.3.2.26, all arcs fit in one byte.
+        assert_eq!(der_oid(&[1, 3, 14, 3, 2, 26]), vec![0x06, 0x05, 43, 14, 3, 2, 26]);
+        // An arc >= 128 must be base-128 encoded across multiple bytes.
+        assert_eq!(der_oid(&[1, 2, 840]), vec![0x06, 0x03, 42, 0x86, 0x48]);
+    }
+
+    #[test]
+    fn test_integer_prepends_zero_when_high_bit_set() {
+        assert_eq!(der_integer(&[0x01]), vec![0x02, 0x01, 0x01]);
+        assert_eq!(der_integer(&[0x80]), vec![0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(der_integer(&[]), vec![0x02, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_null_round_trips_through_read_tlv() {
+        let encoded = der_null();
+        let (tag, content, rest) = der_read_tlv(&encoded).unwrap();
+        assert_eq!(tag, 0x05);
+        assert!(content.is_empty());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_read_tlv_leaves_trailing_bytes_for_caller() {
+        let mut data = der_tlv(0x04, &[9, 9]);
+        data.extend_from_slice(&[0xff, 0xff]);
+
+        let (tag, content, rest) = der_read_tlv(&data).unwrap();
+        assert_eq!(tag, 0x04);
+        assert_eq!(content, &[9, 9]);
+        assert_eq!(rest, &[0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_read_tlv_rejects_truncated_input() {
+        assert!(der_read_tlv(&[]).is_err());
+        assert!(der_read_tlv(&[0x04]).is_err());
+        // Declares 5 bytes of content but only provides 2.
+        assert!(der_read_tlv(&[0x04, 0x05, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_expect_der_tag() {
+        assert!(expect_der_tag(0x30, 0x30, "thing").is_ok());
+        assert!(expect_der_tag(0x30, 0x04, "thing").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_ocsp {
+    use super::{
+        build_ocsp_request, der_integer, der_null, der_octet_string, der_oid, der_read_tlv,
+        der_sequence, der_tlv, parse_ocsp_cert_status, OcspCertStatus,
+    };
+    use x509_parser::prelude::{FromDer, X509Certificate};
+
+    fn parse_cert(der: &[u8]) -> X509Certificate<'_> {
+        X509Certificate::from_der(der).unwrap().1
+    }
+
+    // A throwaway self-signed EC cert (CN=test.example.com), just to give
+    // `build_ocsp_request` a `subject()`/`public_key()` to hash.
+    #[rustfmt::skip]
+    const SELF_SIGNED_CERT_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x8a, 0x30, 0x82, 0x01, 0x31, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x3f, 0x6d, 0x3c, 0x76, 0x91, 0xd8, 0x9d, 0xf2, 0xf1,
+        0x37, 0xfd, 0x11, 0x89, 0x54, 0xbd, 0x23, 0x4c, 0x1c, 0x2f, 0xaa, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x1b, 0x31, 0x19, 0x30, 0x17, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x10,
+        0x74, 0x65, 0x73, 0x74, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65,
+        0x2e, 0x63, 0x6f, 0x6d, 0x30, 0x1e, 0x17, 0x0d, 0x32, 0x36, 0x30, 0x37,
+        0x32, 0x38, 0x30, 0x33, 0x30, 0x34, 0x34, 0x33, 0x5a, 0x17, 0x0d, 0x32,
+        0x36, 0x30, 0x37, 0x32, 0x39, 0x30, 0x33, 0x30, 0x34, 0x34, 0x33, 0x5a,
+        0x30, 0x1b, 0x31, 0x19, 0x30, 0x17, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c,
+        0x10, 0x74, 0x65, 0x73, 0x74, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c,
+        0x65, 0x2e, 0x63, 0x6f, 0x6d, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a,
+        0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04, 0xf3, 0x3d, 0xe4, 0x0c,
+        0x4a, 0x70, 0x9a, 0xb1, 0x85, 0x90, 0x05, 0x5a, 0xb0, 0xc4, 0xee, 0x10,
+        0x24, 0xf5, 0x12, 0xf6, 0x47, 0x3d, 0x92, 0x67, 0xc5, 0xef, 0x89, 0xdd,
+        0x3c, 0x73, 0xbe, 0x52, 0x5d, 0xdf, 0xc8, 0x7b, 0x55, 0x70, 0xbd, 0x5a,
+        0xc2, 0x9a, 0x7e, 0xa6, 0xba, 0x2d, 0xc2, 0x59, 0x84, 0x0b, 0xc2, 0xe3,
+        0x2d, 0xc9, 0x7f, 0x5a, 0x26, 0xd5, 0x50, 0x35, 0x8e, 0xd2, 0xb5, 0x63,
+        0xa3, 0x53, 0x30, 0x51, 0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04,
+        0x16, 0x04, 0x14, 0x37, 0xbf, 0x3c, 0x15, 0x3f, 0xe7, 0x70, 0x29, 0x1f,
+        0xce, 0x46, 0xfa, 0x9a, 0x39, 0xd5, 0x52, 0xfe, 0x27, 0xf0, 0x6e, 0x30,
+        0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14,
+        0x37, 0xbf, 0x3c, 0x15, 0x3f, 0xe7, 0x70, 0x29, 0x1f, 0xce, 0x46, 0xfa,
+        0x9a, 0x39, 0xd5, 0x52, 0xfe, 0x27, 0xf0, 0x6e, 0x30, 0x0f, 0x06, 0x03,
+        0x55, 0x1d, 0x13, 0x01, 0x01, 0xff, 0x04, 0x05, 0x30, 0x03, 0x01, 0x01,
+        0xff, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03,
+        0x02, 0x03, 0x47, 0x00, 0x30, 0x44, 0x02, 0x20, 0x18, 0x37, 0xc1, 0xf0,
+        0x95, 0xc7, 0xda, 0x14, 0xb3, 0x0d, 0x69, 0xdc, 0xaa, 0x24, 0x04, 0x34,
+        0x27, 0x45, 0x5d, 0x26, 0xb5, 0x2f, 0x92, 0x0a, 0xe4, 0xfa, 0xf8, 0xc1,
+        0x4b, 0xfe, 0xfc, 0x45, 0x02, 0x20, 0x49, 0x87, 0x73, 0x74, 0x75, 0xec,
+        0xdf, 0xd2, 0xef, 0x7c, 0x28, 0x31, 0xd4, 0x84, 0x58, 0x55, 0x37, 0x1a,
+        0xb9, 0x5b, 0x42, 0x4d, 0x95, 0x8d, 0x0b, 0x6e, 0x2c, 0x1d, 0x2d, 0xc2,
+        0x82, 0xd4,
+    ];
+
+    #[test]
+    fn test_build_ocsp_request_wraps_tbs_request_in_outer_sequence() {
+        let cert = parse_cert(SELF_SIGNED_CERT_DER);
+        let request = build_ocsp_request(&cert, &cert);
+
+        // OCSPRequest ::= SEQUENCE { tbsRequest TBSRequest, ... }
+        let (outer_tag, ocsp_request, trailing) = der_read_tlv(&request).unwrap();
+        assert_eq!(outer_tag, 0x30);
+        assert!(trailing.is_empty());
+
+        // TBSRequest ::= SEQUENCE { requestList SEQUENCE OF Request, ... }
+        let (tbs_tag, tbs_request, _) = der_read_tlv(ocsp_request).unwrap();
+        assert_eq!(tbs_tag, 0x30);
+        let (list_tag, request_list, _) = der_read_tlv(tbs_request).unwrap();
+        assert_eq!(list_tag, 0x30);
+        let (single_tag, single_request, _) = der_read_tlv(request_list).unwrap();
+        assert_eq!(single_tag, 0x30);
+
+        // Request ::= SEQUENCE { reqCert CertID, ... } -- CertID directly,
+        // since singleRequestExtensions is omitted.
+        let (cert_id_tag, cert_id, _) = der_read_tlv(single_request).unwrap();
+        assert_eq!(cert_id_tag, 0x30);
+        let (hash_alg_tag, _, after_hash_alg) = der_read_tlv(cert_id).unwrap();
+        assert_eq!(hash_alg_tag, 0x30);
+        let (name_hash_tag, name_hash, after_name_hash) = der_read_tlv(after_hash_alg).unwrap();
+        assert_eq!(name_hash_tag, 0x04);
+        assert_eq!(name_hash.len(), 20); // SHA-1 digest
+        let (key_hash_tag, key_hash, after_key_hash) = der_read_tlv(after_name_hash).unwrap();
+        assert_eq!(key_hash_tag, 0x04);
+        assert_eq!(key_hash.len(), 20);
+        let (serial_tag, _, _) = der_read_tlv(after_key_hash).unwrap();
+        assert_eq!(serial_tag, 0x02);
+    }
+
+    fn generalized_time() -> Vec<u8> {
+        der_tlv(0x18, b"20240101000000Z")
+    }
+
+    fn fake_cert_id() -> Vec<u8> {
+        der_sequence(&[
+            der_sequence(&[der_oid(&[1, 3, 14, 3, 2, 26]), der_null()]),
+            der_octet_string(&[0u8; 20]),
+            der_octet_string(&[0u8; 20]),
+            der_integer(&[1]),
+        ])
+    }
+
+    fn fake_single_response(cert_status: Vec<u8>) -> Vec<u8> {
+        der_sequence(&[fake_cert_id(), cert_status, generalized_time()])
+    }
+
+    fn fake_basic_response(cert_status: Vec<u8>) -> Vec<u8> {
+        let responder_id = der_tlv(0xa2, &der_octet_string(&[0u8; 20])); // byKey
+        let tbs_response_data = der_sequence(&[
+            responder_id,
+            generalized_time(),
+            der_sequence(&[fake_single_response(cert_status)]),
+        ]);
+        let signature_algorithm = der_sequence(&[der_oid(&[1, 2, 840, 113549, 1, 1, 11]), der_null()]);
+        let signature = der_tlv(0x03, &[0x00]);
+        der_sequence(&[tbs_response_data, signature_algorithm, signature])
+    }
+
+    fn fake_ocsp_response(status: u8, basic_response: Option<Vec<u8>>) -> Vec<u8> {
+        let mut items = vec![der_tlv(0x0a, &[status])];
+        if let Some(basic_response) = basic_response {
+            let response_bytes = der_sequence(&[
+                der_oid(&[1, 3, 6, 1, 5, 5, 7, 48, 1, 1]), // id-pkix-ocsp-basic
+                der_octet_string(&basic_response),
+            ]);
+            items.push(der_tlv(0xa0, &response_bytes));
+        }
+        der_sequence(&items)
+    }
+
+    #[test]
+    fn test_parse_good_status() {
+        let good = der_tlv(0x80, &[]);
+        let response = fake_ocsp_response(0x00, Some(fake_basic_response(good)));
+        assert_eq!(parse_ocsp_cert_status(&response), Ok(OcspCertStatus::Good));
+    }
+
+    #[test]
+    fn test_parse_revoked_status() {
+        let revoked = der_tlv(0xa1, &generalized_time());
+        let response = fake_ocsp_response(0x00, Some(fake_basic_response(revoked)));
+        assert_eq!(parse_ocsp_cert_status(&response), Ok(OcspCertStatus::Revoked));
+    }
+
+    #[test]
+    fn test_parse_unknown_status() {
+        let unknown = der_tlv(0x82, &[]);
+        let response = fake_ocsp_response(0x00, Some(fake_basic_response(unknown)));
+        assert_eq!(parse_ocsp_cert_status(&response), Ok(OcspCertStatus::Unknown));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_successful_response_status() {
+        // 1 == malformedRequest
+        let response = fake_ocsp_response(0x01, None);
+        assert!(parse_ocsp_cert_status(&response).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_response() {
+        assert!(parse_ocsp_cert_status(&[0x30, 0x01]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_check_chain_of_trust {
+    use super::{check_chain_of_trust, SimpleCheckResult};
+    use x509_parser::prelude::{FromDer, X509Certificate};
+
+    fn parse(der: &[u8]) -> X509Certificate<'_> {
+        X509Certificate::from_der(der).unwrap().1
+    }
+
+    // A throwaway 3-tier PKI (root -> intermediate -> leaf), plus a few
+    // variants used to exercise the failure paths below. All but the expired
+    // intermediate (valid 2020-01-01 to 2020-01-02 only) were generated with
+    // a 2020-2035 validity window so the tests don't rot as time passes.
+
+    #[rustfmt::skip]
+    const ROOT_CA_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x93, 0x30, 0x82, 0x01, 0x39, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x53, 0x9a, 0x58, 0x9a, 0xa1, 0xdd, 0xba, 0xe1, 0x21,
+        0xf7, 0x2c, 0xa1, 0x44, 0xce, 0x8a, 0xd6, 0xd6, 0xd7, 0xf6, 0x4d, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x17, 0x31, 0x15, 0x30, 0x13, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0c,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43, 0x41,
+        0x30, 0x1e, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x30, 0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30,
+        0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x17, 0x31, 0x15,
+        0x30, 0x13, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0c, 0x54, 0x65, 0x73,
+        0x74, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43, 0x41, 0x30, 0x59, 0x30,
+        0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04,
+        0x23, 0x1a, 0x0b, 0x72, 0xa7, 0xa7, 0x0d, 0x16, 0x14, 0xda, 0xb8, 0x88,
+        0x15, 0x49, 0xe0, 0x3b, 0x4a, 0xfe, 0xee, 0x8f, 0xd6, 0xde, 0x53, 0xb2,
+        0x57, 0x59, 0xf7, 0xab, 0x4b, 0xf7, 0x9d, 0x48, 0x2a, 0x88, 0xa1, 0x2a,
+        0x83, 0x17, 0x90, 0xdc, 0xfc, 0x2a, 0x3d, 0x45, 0x27, 0xf2, 0xd7, 0x00,
+        0xf3, 0xcc, 0xb7, 0x06, 0xb8, 0xbc, 0xbf, 0x46, 0xb7, 0xcf, 0x83, 0xb1,
+        0x67, 0xdb, 0xfe, 0x31, 0xa3, 0x63, 0x30, 0x61, 0x30, 0x1d, 0x06, 0x03,
+        0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0x1f, 0xf3, 0xe6, 0x98, 0xcc,
+        0x8a, 0x28, 0x8a, 0xde, 0xcf, 0x64, 0x01, 0x06, 0xcf, 0xb1, 0x7f, 0xaf,
+        0x70, 0x59, 0x09, 0x30, 0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18,
+        0x30, 0x16, 0x80, 0x14, 0x1f, 0xf3, 0xe6, 0x98, 0xcc, 0x8a, 0x28, 0x8a,
+        0xde, 0xcf, 0x64, 0x01, 0x06, 0xcf, 0xb1, 0x7f, 0xaf, 0x70, 0x59, 0x09,
+        0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff, 0x04, 0x05,
+        0x30, 0x03, 0x01, 0x01, 0xff, 0x30, 0x0e, 0x06, 0x03, 0x55, 0x1d, 0x0f,
+        0x01, 0x01, 0xff, 0x04, 0x04, 0x03, 0x02, 0x01, 0x06, 0x30, 0x0a, 0x06,
+        0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x03, 0x48, 0x00,
+        0x30, 0x45, 0x02, 0x21, 0x00, 0x98, 0x02, 0x21, 0x12, 0xe5, 0x56, 0x09,
+        0x9f, 0x86, 0x8e, 0x35, 0xb4, 0xd4, 0x51, 0xf2, 0x1b, 0xa1, 0x4f, 0xeb,
+        0x0e, 0x20, 0x65, 0x96, 0x6f, 0xb6, 0x69, 0x38, 0xb9, 0x81, 0x07, 0x26,
+        0xe3, 0x02, 0x20, 0x39, 0x63, 0x9a, 0x38, 0xb0, 0x50, 0xe5, 0x8a, 0x18,
+        0xd0, 0xb6, 0x95, 0xf2, 0x29, 0x31, 0xae, 0xd6, 0x8c, 0x7a, 0x9e, 0x93,
+        0x01, 0x14, 0x80, 0x7f, 0x4a, 0xaf, 0xe1, 0x00, 0x91, 0x38, 0x29,
+    ];
+
+    #[rustfmt::skip]
+    const INTERMEDIATE_CA_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x9e, 0x30, 0x82, 0x01, 0x44, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x3c, 0x10, 0x34, 0xbd, 0xd4, 0xed, 0x36, 0xa7, 0x81,
+        0x17, 0x25, 0x60, 0xf2, 0xd5, 0x75, 0x8e, 0x9b, 0x1c, 0x09, 0xe3, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x17, 0x31, 0x15, 0x30, 0x13, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0c,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43, 0x41,
+        0x30, 0x1e, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x30, 0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30,
+        0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x1f, 0x31, 0x1d,
+        0x30, 0x1b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x14, 0x54, 0x65, 0x73,
+        0x74, 0x20, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6d, 0x65, 0x64, 0x69, 0x61,
+        0x74, 0x65, 0x20, 0x43, 0x41, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a,
+        0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04, 0xdf, 0xe5, 0xb9, 0x83,
+        0xe7, 0xf7, 0xb7, 0x13, 0x7b, 0x3a, 0x46, 0x31, 0x20, 0x8f, 0x1c, 0x60,
+        0x12, 0xb3, 0xad, 0x0f, 0x7e, 0x3a, 0x89, 0x80, 0x84, 0xbb, 0xba, 0xac,
+        0x1f, 0xbb, 0x4d, 0x15, 0x6f, 0xa4, 0x0f, 0xe4, 0x9b, 0xe8, 0xa1, 0x83,
+        0x16, 0x26, 0xae, 0x3f, 0xa8, 0x54, 0x11, 0x2a, 0x4d, 0xd6, 0x9e, 0xd5,
+        0x06, 0x41, 0x69, 0xd7, 0x17, 0x6f, 0x6c, 0xb7, 0x3c, 0x02, 0xec, 0x40,
+        0xa3, 0x66, 0x30, 0x64, 0x30, 0x12, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01,
+        0x01, 0xff, 0x04, 0x08, 0x30, 0x06, 0x01, 0x01, 0xff, 0x02, 0x01, 0x00,
+        0x30, 0x0e, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x01, 0x01, 0xff, 0x04, 0x04,
+        0x03, 0x02, 0x01, 0x06, 0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04,
+        0x16, 0x04, 0x14, 0x4b, 0x09, 0xc6, 0xf7, 0xea, 0x3d, 0x21, 0x9c, 0x16,
+        0x2f, 0x95, 0x68, 0x33, 0x0d, 0x27, 0x24, 0x9d, 0x55, 0x10, 0x07, 0x30,
+        0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14,
+        0x1f, 0xf3, 0xe6, 0x98, 0xcc, 0x8a, 0x28, 0x8a, 0xde, 0xcf, 0x64, 0x01,
+        0x06, 0xcf, 0xb1, 0x7f, 0xaf, 0x70, 0x59, 0x09, 0x30, 0x0a, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x03, 0x48, 0x00, 0x30,
+        0x45, 0x02, 0x20, 0x2c, 0x98, 0x7d, 0x3f, 0xe0, 0x04, 0xc5, 0x63, 0x20,
+        0x2a, 0xe0, 0x7f, 0x2d, 0xd7, 0x90, 0x58, 0xaf, 0xbc, 0x29, 0x10, 0x19,
+        0x48, 0xc2, 0x4f, 0x3a, 0x88, 0xeb, 0xd3, 0x35, 0xd0, 0xfd, 0x2a, 0x02,
+        0x21, 0x00, 0xa3, 0xbc, 0x55, 0x6e, 0x84, 0x91, 0x28, 0x01, 0x88, 0x0d,
+        0x4e, 0xf8, 0x95, 0x01, 0x45, 0x39, 0x24, 0xb5, 0xd3, 0x8b, 0x65, 0x73,
+        0xc6, 0xb4, 0x00, 0x30, 0x89, 0x9b, 0xb9, 0x70, 0x17, 0x4f,
+    ];
+
+    #[rustfmt::skip]
+    const LEAF_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x97, 0x30, 0x82, 0x01, 0x3c, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x67, 0x96, 0x68, 0xc7, 0xea, 0xf8, 0xcc, 0x77, 0x8c,
+        0x7b, 0xed, 0x6c, 0xf7, 0x0e, 0x2e, 0x35, 0x25, 0x63, 0xc4, 0xe9, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x1f, 0x31, 0x1d, 0x30, 0x1b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x14,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6d, 0x65,
+        0x64, 0x69, 0x61, 0x74, 0x65, 0x20, 0x43, 0x41, 0x30, 0x1e, 0x17, 0x0d,
+        0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30,
+        0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x5a, 0x30, 0x1b, 0x31, 0x19, 0x30, 0x17, 0x06, 0x03,
+        0x55, 0x04, 0x03, 0x0c, 0x10, 0x74, 0x65, 0x73, 0x74, 0x2e, 0x65, 0x78,
+        0x61, 0x6d, 0x70, 0x6c, 0x65, 0x2e, 0x63, 0x6f, 0x6d, 0x30, 0x59, 0x30,
+        0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04,
+        0x39, 0xd4, 0xd2, 0x9f, 0x44, 0x2c, 0x79, 0x0e, 0x78, 0x08, 0x66, 0x8d,
+        0xb6, 0xf2, 0x6f, 0xfc, 0x82, 0x7c, 0x35, 0xcb, 0xb6, 0x98, 0xb3, 0x32,
+        0x1d, 0x74, 0x25, 0x9a, 0x69, 0x49, 0x20, 0xab, 0xc0, 0xc7, 0x2e, 0x80,
+        0xf9, 0x76, 0x37, 0xe4, 0x2c, 0xbd, 0xc0, 0x77, 0x03, 0x3c, 0x7b, 0x92,
+        0x81, 0x7c, 0x40, 0x65, 0xd1, 0x46, 0xa7, 0x90, 0x4e, 0xc8, 0x60, 0x1a,
+        0x41, 0x2c, 0xdf, 0x19, 0xa3, 0x5a, 0x30, 0x58, 0x30, 0x09, 0x06, 0x03,
+        0x55, 0x1d, 0x13, 0x04, 0x02, 0x30, 0x00, 0x30, 0x0b, 0x06, 0x03, 0x55,
+        0x1d, 0x0f, 0x04, 0x04, 0x03, 0x02, 0x07, 0x80, 0x30, 0x1d, 0x06, 0x03,
+        0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0xcb, 0x48, 0x59, 0xab, 0x7a,
+        0x9c, 0xb8, 0x6d, 0xc9, 0xad, 0xa3, 0x85, 0xb4, 0x47, 0x06, 0x20, 0x39,
+        0xf4, 0xb3, 0x54, 0x30, 0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18,
+        0x30, 0x16, 0x80, 0x14, 0x4b, 0x09, 0xc6, 0xf7, 0xea, 0x3d, 0x21, 0x9c,
+        0x16, 0x2f, 0x95, 0x68, 0x33, 0x0d, 0x27, 0x24, 0x9d, 0x55, 0x10, 0x07,
+        0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02,
+        0x03, 0x49, 0x00, 0x30, 0x46, 0x02, 0x21, 0x00, 0x8d, 0xaf, 0xbf, 0x72,
+        0x17, 0x0d, 0x66, 0x5e, 0xa4, 0xd0, 0xf5, 0xe5, 0x0e, 0x99, 0x39, 0x96,
+        0x59, 0xf9, 0x1d, 0xb3, 0xb0, 0x60, 0x13, 0xa5, 0x2b, 0xf7, 0x91, 0xe9,
+        0xdf, 0x71, 0xce, 0xf4, 0x02, 0x21, 0x00, 0xa6, 0x05, 0x9e, 0x12, 0xcc,
+        0xbd, 0xac, 0xd3, 0x3c, 0x36, 0xb9, 0x83, 0xf5, 0xee, 0x63, 0x8c, 0xca,
+        0xe3, 0x61, 0x36, 0x00, 0x96, 0x53, 0x93, 0x0b, 0x71, 0xf5, 0x34, 0x33,
+        0x48, 0xd5, 0x3d,
+    ];
+
+    #[rustfmt::skip]
+    const OTHER_ROOT_CA_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x95, 0x30, 0x82, 0x01, 0x3b, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x42, 0x0c, 0xdf, 0x1a, 0xbe, 0xb1, 0xe1, 0x8a, 0x9c,
+        0x1a, 0xdc, 0x15, 0xa0, 0x30, 0xbf, 0x57, 0xcc, 0xc5, 0xdf, 0x2a, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x18, 0x31, 0x16, 0x30, 0x14, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0d,
+        0x4f, 0x74, 0x68, 0x65, 0x72, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43,
+        0x41, 0x30, 0x1e, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30,
+        0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31,
+        0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x18, 0x31,
+        0x16, 0x30, 0x14, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0d, 0x4f, 0x74,
+        0x68, 0x65, 0x72, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43, 0x41, 0x30,
+        0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01,
+        0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42,
+        0x00, 0x04, 0xf8, 0x6c, 0x04, 0x3c, 0x1d, 0x8c, 0x42, 0x9b, 0xc1, 0xa9,
+        0xd2, 0x4e, 0xa8, 0xde, 0x00, 0x4d, 0xe6, 0xb6, 0xb9, 0x17, 0xd7, 0x0d,
+        0x22, 0x5c, 0xeb, 0xad, 0x12, 0xa3, 0x20, 0xca, 0xd2, 0x14, 0x37, 0xef,
+        0x5f, 0x03, 0xad, 0x90, 0x0e, 0x4c, 0xc8, 0x99, 0x8d, 0x12, 0x40, 0x84,
+        0xb0, 0xfd, 0x44, 0xab, 0x56, 0x4a, 0x4e, 0x53, 0x39, 0xb1, 0x0a, 0xd7,
+        0x19, 0xe1, 0xa6, 0x57, 0x49, 0x19, 0xa3, 0x63, 0x30, 0x61, 0x30, 0x1d,
+        0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0xb4, 0xda, 0xc3,
+        0xdb, 0x6f, 0x65, 0xff, 0xe7, 0x1d, 0x27, 0x43, 0x9d, 0xf2, 0x9c, 0xd7,
+        0xcd, 0xb1, 0x1c, 0xe1, 0xe4, 0x30, 0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23,
+        0x04, 0x18, 0x30, 0x16, 0x80, 0x14, 0xb4, 0xda, 0xc3, 0xdb, 0x6f, 0x65,
+        0xff, 0xe7, 0x1d, 0x27, 0x43, 0x9d, 0xf2, 0x9c, 0xd7, 0xcd, 0xb1, 0x1c,
+        0xe1, 0xe4, 0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff,
+        0x04, 0x05, 0x30, 0x03, 0x01, 0x01, 0xff, 0x30, 0x0e, 0x06, 0x03, 0x55,
+        0x1d, 0x0f, 0x01, 0x01, 0xff, 0x04, 0x04, 0x03, 0x02, 0x01, 0x06, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x03,
+        0x48, 0x00, 0x30, 0x45, 0x02, 0x21, 0x00, 0xd0, 0x94, 0x5c, 0xa9, 0xae,
+        0xe1, 0xc1, 0x25, 0x72, 0x22, 0xd4, 0x99, 0x47, 0x87, 0xd3, 0x18, 0x4d,
+        0x99, 0x08, 0xce, 0x95, 0x51, 0x76, 0xef, 0xc3, 0x43, 0x55, 0xfa, 0x51,
+        0xc3, 0xf2, 0xe5, 0x02, 0x20, 0x20, 0xc4, 0x32, 0x2a, 0x13, 0xbf, 0xad,
+        0xd9, 0x8f, 0x1b, 0x05, 0xfb, 0x5b, 0xda, 0x11, 0x27, 0x8d, 0x4a, 0x6f,
+        0x8a, 0x02, 0xbb, 0x2f, 0x25, 0x8c, 0xfa, 0x1d, 0xad, 0x58, 0x4e, 0x19,
+        0xf2,
+    ];
+
+    #[rustfmt::skip]
+    const EXPIRED_INTERMEDIATE_CA_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x9d, 0x30, 0x82, 0x01, 0x44, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x3c, 0x10, 0x34, 0xbd, 0xd4, 0xed, 0x36, 0xa7, 0x81,
+        0x17, 0x25, 0x60, 0xf2, 0xd5, 0x75, 0x8e, 0x9b, 0x1c, 0x09, 0xe4, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x17, 0x31, 0x15, 0x30, 0x13, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0c,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x52, 0x6f, 0x6f, 0x74, 0x20, 0x43, 0x41,
+        0x30, 0x1e, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x30, 0x5a, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30,
+        0x32, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x1f, 0x31, 0x1d,
+        0x30, 0x1b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x14, 0x54, 0x65, 0x73,
+        0x74, 0x20, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6d, 0x65, 0x64, 0x69, 0x61,
+        0x74, 0x65, 0x20, 0x43, 0x41, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a,
+        0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04, 0xdf, 0xe5, 0xb9, 0x83,
+        0xe7, 0xf7, 0xb7, 0x13, 0x7b, 0x3a, 0x46, 0x31, 0x20, 0x8f, 0x1c, 0x60,
+        0x12, 0xb3, 0xad, 0x0f, 0x7e, 0x3a, 0x89, 0x80, 0x84, 0xbb, 0xba, 0xac,
+        0x1f, 0xbb, 0x4d, 0x15, 0x6f, 0xa4, 0x0f, 0xe4, 0x9b, 0xe8, 0xa1, 0x83,
+        0x16, 0x26, 0xae, 0x3f, 0xa8, 0x54, 0x11, 0x2a, 0x4d, 0xd6, 0x9e, 0xd5,
+        0x06, 0x41, 0x69, 0xd7, 0x17, 0x6f, 0x6c, 0xb7, 0x3c, 0x02, 0xec, 0x40,
+        0xa3, 0x66, 0x30, 0x64, 0x30, 0x12, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01,
+        0x01, 0xff, 0x04, 0x08, 0x30, 0x06, 0x01, 0x01, 0xff, 0x02, 0x01, 0x00,
+        0x30, 0x0e, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x01, 0x01, 0xff, 0x04, 0x04,
+        0x03, 0x02, 0x01, 0x06, 0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04,
+        0x16, 0x04, 0x14, 0x4b, 0x09, 0xc6, 0xf7, 0xea, 0x3d, 0x21, 0x9c, 0x16,
+        0x2f, 0x95, 0x68, 0x33, 0x0d, 0x27, 0x24, 0x9d, 0x55, 0x10, 0x07, 0x30,
+        0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14,
+        0x1f, 0xf3, 0xe6, 0x98, 0xcc, 0x8a, 0x28, 0x8a, 0xde, 0xcf, 0x64, 0x01,
+        0x06, 0xcf, 0xb1, 0x7f, 0xaf, 0x70, 0x59, 0x09, 0x30, 0x0a, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x03, 0x47, 0x00, 0x30,
+        0x44, 0x02, 0x20, 0x08, 0xeb, 0x37, 0x7e, 0x51, 0xfc, 0x90, 0xd4, 0x0b,
+        0xdf, 0xdc, 0x6b, 0x39, 0x09, 0x4a, 0xe6, 0xd9, 0x0b, 0x67, 0x37, 0x89,
+        0x13, 0xac, 0xe3, 0x4d, 0x5e, 0xe7, 0xc1, 0xe9, 0xaf, 0x75, 0xb0, 0x02,
+        0x20, 0x7a, 0x53, 0xb4, 0xbe, 0x41, 0xc0, 0x9a, 0x3f, 0x20, 0x9e, 0x78,
+        0xf1, 0x54, 0xc8, 0x06, 0x33, 0x6a, 0x2d, 0x43, 0xc4, 0x17, 0xa6, 0xa3,
+        0x3e, 0x2a, 0x73, 0xf2, 0x77, 0x1b, 0xcf, 0xff, 0xaa,
+    ];
+
+    #[rustfmt::skip]
+    const MID_CA_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x9a, 0x30, 0x82, 0x01, 0x40, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x67, 0x96, 0x68, 0xc7, 0xea, 0xf8, 0xcc, 0x77, 0x8c,
+        0x7b, 0xed, 0x6c, 0xf7, 0x0e, 0x2e, 0x35, 0x25, 0x63, 0xc4, 0xea, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x1f, 0x31, 0x1d, 0x30, 0x1b, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x14,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6d, 0x65,
+        0x64, 0x69, 0x61, 0x74, 0x65, 0x20, 0x43, 0x41, 0x30, 0x1e, 0x17, 0x0d,
+        0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30,
+        0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x5a, 0x30, 0x16, 0x31, 0x14, 0x30, 0x12, 0x06, 0x03,
+        0x55, 0x04, 0x03, 0x0c, 0x0b, 0x54, 0x65, 0x73, 0x74, 0x20, 0x4d, 0x69,
+        0x64, 0x20, 0x43, 0x41, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86,
+        0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d,
+        0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04, 0x28, 0xae, 0xd7, 0xbe, 0x54,
+        0xc1, 0x75, 0x92, 0x77, 0xf1, 0xb5, 0x89, 0x97, 0x06, 0x12, 0xf5, 0x09,
+        0x23, 0x66, 0xea, 0xcd, 0x0f, 0x1d, 0x69, 0x72, 0x42, 0x2f, 0x4f, 0x0d,
+        0x69, 0x1e, 0x83, 0x55, 0x32, 0xeb, 0xd9, 0x29, 0x3a, 0xfe, 0x05, 0x86,
+        0x96, 0x71, 0x7c, 0x91, 0xb7, 0x96, 0x3e, 0xcd, 0xb5, 0x9f, 0x27, 0x2b,
+        0xf9, 0xcc, 0x78, 0xac, 0xaf, 0xc2, 0x0c, 0x43, 0x6b, 0x44, 0xe3, 0xa3,
+        0x63, 0x30, 0x61, 0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01,
+        0xff, 0x04, 0x05, 0x30, 0x03, 0x01, 0x01, 0xff, 0x30, 0x0e, 0x06, 0x03,
+        0x55, 0x1d, 0x0f, 0x01, 0x01, 0xff, 0x04, 0x04, 0x03, 0x02, 0x01, 0x06,
+        0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0xc8,
+        0x67, 0x19, 0x08, 0xb5, 0x4d, 0xa9, 0x97, 0x12, 0x85, 0xc7, 0x58, 0xdd,
+        0x82, 0x8a, 0x1a, 0x21, 0xe4, 0xe6, 0xb9, 0x30, 0x1f, 0x06, 0x03, 0x55,
+        0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14, 0x4b, 0x09, 0xc6, 0xf7,
+        0xea, 0x3d, 0x21, 0x9c, 0x16, 0x2f, 0x95, 0x68, 0x33, 0x0d, 0x27, 0x24,
+        0x9d, 0x55, 0x10, 0x07, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x04, 0x03, 0x02, 0x03, 0x48, 0x00, 0x30, 0x45, 0x02, 0x20, 0x2e,
+        0x20, 0x8f, 0x75, 0xbc, 0x25, 0xeb, 0x9b, 0x7d, 0x2e, 0x7b, 0x1e, 0x45,
+        0x34, 0x79, 0xaa, 0x5c, 0x55, 0x3f, 0x27, 0xf6, 0xf2, 0xcb, 0xb5, 0xd2,
+        0x23, 0xd8, 0xd7, 0xb5, 0xbc, 0x17, 0xb2, 0x02, 0x21, 0x00, 0x83, 0xe9,
+        0x58, 0xb7, 0x86, 0x44, 0x4a, 0xfe, 0xe0, 0x4e, 0x7f, 0x6f, 0x03, 0x92,
+        0x51, 0x19, 0x86, 0x7f, 0x9e, 0xe7, 0xb2, 0x37, 0x39, 0x55, 0xea, 0xb6,
+        0xac, 0xd1, 0x45, 0x9a, 0xf9, 0xa4,
+    ];
+
+    #[rustfmt::skip]
+    const LEAF2_DER: &[u8] = &[
+        0x30, 0x82, 0x01, 0x8d, 0x30, 0x82, 0x01, 0x34, 0xa0, 0x03, 0x02, 0x01,
+        0x02, 0x02, 0x14, 0x26, 0x2d, 0x6a, 0x98, 0x63, 0x42, 0xe4, 0xdf, 0xf3,
+        0x6e, 0x07, 0x66, 0xd6, 0x96, 0xcc, 0xaf, 0x13, 0xa9, 0xff, 0x2b, 0x30,
+        0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30,
+        0x16, 0x31, 0x14, 0x30, 0x12, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x0b,
+        0x54, 0x65, 0x73, 0x74, 0x20, 0x4d, 0x69, 0x64, 0x20, 0x43, 0x41, 0x30,
+        0x1e, 0x17, 0x0d, 0x32, 0x30, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30, 0x30,
+        0x30, 0x30, 0x30, 0x5a, 0x17, 0x0d, 0x33, 0x35, 0x30, 0x31, 0x30, 0x31,
+        0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x1c, 0x31, 0x1a, 0x30,
+        0x18, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x11, 0x6c, 0x65, 0x61, 0x66,
+        0x32, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x2e, 0x63, 0x6f,
+        0x6d, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d,
+        0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07,
+        0x03, 0x42, 0x00, 0x04, 0x70, 0x15, 0xe2, 0x48, 0x27, 0xf7, 0x87, 0xc9,
+        0xed, 0xe0, 0x70, 0x39, 0x8a, 0xbb, 0xc6, 0xaf, 0x10, 0x6c, 0x0a, 0xcd,
+        0x9d, 0xe7, 0x7a, 0x21, 0xb8, 0x3e, 0x52, 0x39, 0x97, 0xbc, 0x04, 0xe0,
+        0x7b, 0x1e, 0xc7, 0x21, 0xac, 0x7a, 0x86, 0xc0, 0x9f, 0xa0, 0x14, 0xa2,
+        0x95, 0x1f, 0xaa, 0xf5, 0xc5, 0x5c, 0x2d, 0xf1, 0x39, 0x73, 0x40, 0x0a,
+        0x7e, 0xbb, 0x88, 0xd5, 0x5f, 0x0f, 0xde, 0x8a, 0xa3, 0x5a, 0x30, 0x58,
+        0x30, 0x09, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x04, 0x02, 0x30, 0x00, 0x30,
+        0x0b, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x04, 0x04, 0x03, 0x02, 0x07, 0x80,
+        0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0xb6,
+        0x4e, 0xee, 0xcf, 0x63, 0x4a, 0xdd, 0xab, 0x4b, 0x16, 0x38, 0xce, 0x16,
+        0x7b, 0xf1, 0xe0, 0x4c, 0xd7, 0x82, 0x6c, 0x30, 0x1f, 0x06, 0x03, 0x55,
+        0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14, 0xc8, 0x67, 0x19, 0x08,
+        0xb5, 0x4d, 0xa9, 0x97, 0x12, 0x85, 0xc7, 0x58, 0xdd, 0x82, 0x8a, 0x1a,
+        0x21, 0xe4, 0xe6, 0xb9, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x04, 0x03, 0x02, 0x03, 0x47, 0x00, 0x30, 0x44, 0x02, 0x20, 0x6e,
+        0xba, 0x4d, 0xc6, 0x7c, 0x34, 0xe1, 0x7d, 0xc1, 0x01, 0xdc, 0xcc, 0x67,
+        0x1a, 0x7e, 0x6f, 0x37, 0xb2, 0x14, 0xc7, 0xf3, 0x8d, 0x55, 0x61, 0x6d,
+        0x3f, 0x3b, 0x74, 0xa1, 0x58, 0xfe, 0x0b, 0x02, 0x20, 0x2d, 0x92, 0x85,
+        0x64, 0x4f, 0x06, 0xa3, 0xb9, 0xa5, 0xdb, 0x0e, 0xf2, 0x69, 0x52, 0x0e,
+        0x5b, 0x74, 0x54, 0x9b, 0xb4, 0x03, 0x6f, 0xdd, 0x57, 0xe9, 0x0c, 0x51,
+        0xff, 0x93, 0x8d, 0x07, 0x03,
+    ];
+
+    #[test]
+    fn chain_verified_up_to_trust_anchor() {
+        let leaf = parse(LEAF_DER);
+        let intermediate = parse(INTERMEDIATE_CA_DER);
+        let result = check_chain_of_trust(
+            &[leaf, intermediate],
+            Some(vec![ROOT_CA_DER.to_vec()]),
+        );
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::notice(
+                "Certificate chain verified up to trust anchor \"CN=Test Root CA\""
+            ))
+        );
+    }
+
+    #[test]
+    fn broken_chain_is_rejected() {
+        // leaf2 was issued by the "mid" CA, not by `intermediate` -- its
+        // issuer DN doesn't match `intermediate`'s subject DN.
+        let leaf2 = parse(LEAF2_DER);
+        let intermediate = parse(INTERMEDIATE_CA_DER);
+        let result = check_chain_of_trust(
+            &[leaf2, intermediate],
+            Some(vec![ROOT_CA_DER.to_vec()]),
+        );
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::crit(
+                "Chain broken at depth 0: issuer \"CN=Test Mid CA\" does not match subject \"CN=Test Intermediate CA\""
+            ))
+        );
+    }
+
+    #[test]
+    fn untrusted_root_is_rejected() {
+        let leaf = parse(LEAF_DER);
+        let intermediate = parse(INTERMEDIATE_CA_DER);
+        // The configured anchor set doesn't contain the real root at all.
+        let result = check_chain_of_trust(
+            &[leaf, intermediate],
+            Some(vec![OTHER_ROOT_CA_DER.to_vec()]),
+        );
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::crit(
+                "Certificate chain cannot be completed up to a configured trust anchor"
+            ))
+        );
+    }
+
+    #[test]
+    fn non_self_signed_anchor_is_rejected() {
+        // The anchor's subject matches the leaf's issuer, but the anchor
+        // itself isn't self-signed (its subject != its issuer), so it can't
+        // terminate the chain.
+        let leaf = parse(LEAF_DER);
+        let result = check_chain_of_trust(&[leaf], Some(vec![INTERMEDIATE_CA_DER.to_vec()]));
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::crit(
+                "Root of chain is untrusted: not self-signed"
+            ))
+        );
+    }
+
+    #[test]
+    fn path_len_constraint_violation_is_rejected() {
+        // leaf2 -> mid -> intermediate (pathlen:0) -> root: `intermediate`
+        // only permits zero intermediates below it, but `mid` is one.
+        let leaf2 = parse(LEAF2_DER);
+        let mid = parse(MID_CA_DER);
+        let intermediate = parse(INTERMEDIATE_CA_DER);
+        let result = check_chain_of_trust(
+            &[leaf2, mid, intermediate],
+            Some(vec![ROOT_CA_DER.to_vec()]),
+        );
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::crit(
+                "pathLenConstraint of 0 exceeded at depth 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn expired_intermediate_is_rejected() {
+        let leaf = parse(LEAF_DER);
+        let expired_intermediate = parse(EXPIRED_INTERMEDIATE_CA_DER);
+        let not_after = expired_intermediate.validity().not_after;
+        let result = check_chain_of_trust(
+            &[leaf, expired_intermediate],
+            Some(vec![ROOT_CA_DER.to_vec()]),
+        );
+        assert_eq!(
+            result,
+            Some(SimpleCheckResult::crit(format!(
+                "Certificate at depth 1 is not temporally valid ({})",
+                not_after
+            )))
+        );
+    }
+
+    #[test]
+    fn no_trust_anchors_configured_skips_the_check() {
+        let leaf = parse(LEAF_DER);
+        assert_eq!(check_chain_of_trust(&[leaf], None), None);
+    }
+}
+
@@ -6,8 +6,9 @@ use crate::config::{self, CheckConfig};
 use crate::emit::header;
 use crate::ms_sql::queries;
 use anyhow::Result;
+use std::path::Path;
 
-use tiberius::{AuthMethod, Client, Config, Query, Row, SqlBrowser};
+use tiberius::{AuthMethod, Client, Config, EncryptionLevel, Query, Row, SqlBrowser};
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
@@ -15,10 +16,173 @@ use super::defaults;
 
 pub const SQL_LOGIN_ERROR_TAG: &str = "[SQL LOGIN ERROR]";
 pub const SQL_TCP_ERROR_TAG: &str = "[SQL TCP ERROR]";
+pub const SQL_TLS_ERROR_TAG: &str = "[SQL TLS ERROR]";
 
 pub enum Credentials<'a> {
     SqlServer { user: &'a str, password: &'a str },
     Windows { user: &'a str, password: &'a str },
+    /// Azure AD / Azure SQL token authentication, for instances (e.g. Azure
+    /// SQL Managed Instance, Azure SQL Database) where SQL logins are
+    /// disabled and integrated Windows auth is unavailable on unix agents.
+    AadToken { token: &'a str },
+    AadPassword { user: &'a str, password: &'a str },
+}
+
+/// How strictly to validate the server's certificate during the TLS
+/// handshake tiberius performs before login.
+///
+/// Deliberately does not derive `Default`: there is no safe default here,
+/// callers must pick a mode (see `EncryptionConfig`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EncryptionMode {
+    /// Encrypt the login procedure only (tiberius `EncryptionLevel::Off`).
+    Off,
+    /// Encrypt the whole connection if the server supports it (tiberius
+    /// `EncryptionLevel::On`).
+    On,
+    /// Encrypt the whole connection, trusting whatever cert the server
+    /// presents. This is what every constructor did unconditionally before
+    /// `EncryptionConfig` existed -- keep it available, but opt-in only.
+    Required,
+    /// Encrypt the whole connection and verify the server certificate
+    /// against `ca_bundle_path` (or the platform trust store, if unset),
+    /// additionally checking `server_hostname` against the cert's SANs.
+    Strict,
+}
+
+/// Controls whether/how a MS SQL connection is encrypted.
+///
+/// `Strict` is the only mode that performs real certificate validation;
+/// `Required` merely turns encryption on and accepts any certificate
+/// (equivalent to tiberius's `trust_cert()`), which is fine for a trusted
+/// network but must be an explicit choice rather than the default.
+///
+/// Deliberately does not derive `Default`: `Default::default()` must not be
+/// able to hand a caller the insecure mode without going through
+/// `EncryptionConfig::insecure()` by name.
+#[derive(Clone, Debug)]
+pub struct EncryptionConfig {
+    pub mode: EncryptionMode,
+    pub ca_bundle_path: Option<std::path::PathBuf>,
+    pub server_hostname: Option<String>,
+}
+
+impl EncryptionConfig {
+    /// The old, insecure-by-default behavior: encrypted, but any server
+    /// certificate is trusted. Reach for this explicitly, not as a default.
+    pub fn insecure() -> Self {
+        Self {
+            mode: EncryptionMode::Required,
+            ca_bundle_path: None,
+            server_hostname: None,
+        }
+    }
+}
+
+/// Apply `encryption` to `config`.
+///
+/// Callers MUST resolve the real connect address (`config.get_addr()`, or
+/// run SQL Browser discovery) *before* calling this: in `Strict` mode with
+/// `server_hostname` set, this overwrites `config.host()` so that tiberius's
+/// TLS handshake verifies the cert against `server_hostname` rather than the
+/// address the caller actually connects to ("connect by IP, verify a DNS
+/// name" setups). Applying it earlier would silently redirect the TCP
+/// connect itself, and for named instances would break SQL Browser
+/// discovery, which broadcasts to the real `host`.
+fn apply_encryption(config: &mut Config, encryption: &EncryptionConfig) -> Result<(), MsSqlError> {
+    config.encryption(match encryption.mode {
+        EncryptionMode::Off => EncryptionLevel::Off,
+        EncryptionMode::On => EncryptionLevel::On,
+        EncryptionMode::Required | EncryptionMode::Strict => EncryptionLevel::Required,
+    });
+
+    match encryption.mode {
+        EncryptionMode::Strict => {
+            if let Some(ca_bundle_path) = &encryption.ca_bundle_path {
+                let pem = read_ca_bundle(ca_bundle_path)?;
+                config.trust_cert_ca(pem);
+            }
+            if let Some(hostname) = &encryption.server_hostname {
+                config.host(hostname);
+            }
+        }
+        EncryptionMode::Off | EncryptionMode::On | EncryptionMode::Required => {
+            // Trust the server cert unconditionally, matching the
+            // pre-`EncryptionConfig` behavior: not safe for production, but
+            // it is what callers are opting into by not choosing `Strict`.
+            config.trust_cert();
+        }
+    }
+    Ok(())
+}
+
+fn read_ca_bundle(path: &Path) -> Result<Vec<u8>, MsSqlError> {
+    std::fs::read(path)
+        .map_err(|e| MsSqlError::Tls(format!("failed to read CA bundle {:?}: {}", path, e)))
+}
+
+/// Structured MS SQL connection/query errors, categorized so callers can
+/// branch on the nature of a failure (e.g. "login failed" vs "instance
+/// unreachable") instead of pattern-matching on a flattened, tagged string.
+///
+/// SQL error numbers are tiberius `TokenError` codes, e.g. 18456
+/// (login failed) and 4060 (cannot open the requested database).
+#[derive(Debug, thiserror::Error)]
+pub enum MsSqlError {
+    #[error("{SQL_TCP_ERROR_TAG} {0}")]
+    Transport(String),
+
+    #[error("{SQL_TLS_ERROR_TAG} {0}")]
+    Tls(String),
+
+    #[error("{SQL_LOGIN_ERROR_TAG} login failed (SQL error {code}): {message}")]
+    LoginFailed { code: u32, message: String },
+
+    #[error("{SQL_LOGIN_ERROR_TAG} database unavailable (SQL error {code}): {message}")]
+    DatabaseUnavailable { code: u32, message: String },
+
+    #[error("permission denied (SQL error {code}): {message}")]
+    PermissionDenied { code: u32, message: String },
+
+    #[error("query failed (SQL error {code}): {message}")]
+    Query { code: u32, message: String },
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<std::io::Error> for MsSqlError {
+    fn from(err: std::io::Error) -> Self {
+        MsSqlError::Transport(err.to_string())
+    }
+}
+
+impl From<tiberius::error::Error> for MsSqlError {
+    fn from(err: tiberius::error::Error) -> Self {
+        match &err {
+            tiberius::error::Error::Server(token) => {
+                let code = token.code();
+                let message = token.message().to_string();
+                match code {
+                    18456 => MsSqlError::LoginFailed { code, message },
+                    4060 => MsSqlError::DatabaseUnavailable { code, message },
+                    229 | 230 | 300 => MsSqlError::PermissionDenied { code, message },
+                    _ => MsSqlError::Query { code, message },
+                }
+            }
+            // rustls/tokio-rustls surface handshake and certificate
+            // validation failures (untrusted chain, hostname mismatch, ...)
+            // as an `io::Error` of kind `InvalidData`; anything else of this
+            // shape (reset connections, EOF, ...) is a plain transport
+            // failure, not a TLS one.
+            tiberius::error::Error::Io(io_err)
+                if io_err.kind() == std::io::ErrorKind::InvalidData =>
+            {
+                MsSqlError::Tls(err.to_string())
+            }
+            _ => MsSqlError::Transport(err.to_string()),
+        }
+    }
 }
 
 pub struct Section {
@@ -130,12 +294,13 @@ fn get_section_separator(name: &str) -> Option<char> {
 /// * `host` - Hostname of MS SQL server
 /// * `port` - Port of MS SQL server
 /// * `credentials` - defines connection type and credentials itself
-/// * `instance_name` - name of the instance to connect to
+/// * `encryption` - TLS mode and, for `Strict`, how to validate the server cert
 pub async fn create_client(
     host: &str,
     port: u16,
     credentials: Credentials<'_>,
-) -> Result<Client<Compat<TcpStream>>> {
+    encryption: &EncryptionConfig,
+) -> Result<Client<Compat<TcpStream>>, MsSqlError> {
     let mut config = Config::new();
 
     config.host(host);
@@ -148,11 +313,18 @@ pub async fn create_client(
         Credentials::Windows {
             user: _,
             password: _,
-        } => anyhow::bail!("not supported"),
+        } => return Err(MsSqlError::Other("not supported".to_string())),
+        Credentials::AadToken { token } => AuthMethod::aad_token(token),
+        Credentials::AadPassword { user, password } => AuthMethod::aad_password(user, password),
     });
-    config.trust_cert(); // on production, it is not a good idea to do this
 
-    let tcp = TcpStream::connect(config.get_addr()).await?;
+    // Resolve the real connect address before `apply_encryption`, which in
+    // `Strict` mode may repoint `config`'s host at `server_hostname` for TLS
+    // verification -- the actual TCP connect must still go to `host`.
+    let addr = config.get_addr();
+    apply_encryption(&mut config, encryption)?;
+
+    let tcp = TcpStream::connect(addr).await?;
     tcp.set_nodelay(true)?;
 
     // To be able to use Tokio's tcp, we're using the `compat_write` from
@@ -169,12 +341,14 @@ pub async fn create_client(
 /// * `port` - Port of MS SQL server BROWSER,  1434 - default
 /// * `credentials` - defines connection type and credentials itself
 /// * `instance_name` - name of the instance to connect to
+/// * `encryption` - TLS mode and, for `Strict`, how to validate the server cert
 pub async fn create_client_for_instance(
     host: &str,
     port: Option<u16>,
     credentials: Credentials<'_>,
     instance_name: &str,
-) -> anyhow::Result<Client<Compat<TcpStream>>> {
+    encryption: &EncryptionConfig,
+) -> Result<Client<Compat<TcpStream>>, MsSqlError> {
     let mut config = Config::new();
 
     config.host(host);
@@ -188,25 +362,28 @@ pub async fn create_client_for_instance(
         Credentials::Windows {
             user: _,
             password: _,
-        } => anyhow::bail!("not supported"),
+        } => return Err(MsSqlError::Other("not supported".to_string())),
+        Credentials::AadToken { token } => AuthMethod::aad_token(token),
+        Credentials::AadPassword { user, password } => AuthMethod::aad_password(user, password),
     });
 
     // The name of the database server instance.
     config.instance_name(instance_name);
 
-    // on production, it is not a good idea to do this
-    config.trust_cert();
-
+    // SQL Browser discovery broadcasts to the real `host`, so it must run
+    // before `apply_encryption`, which in `Strict` mode may repoint
+    // `config`'s host at `server_hostname` for TLS verification.
+    //
     // This will create a new `TcpStream` from `async-std`, connected to the
     // right port of the named instance.
     let tcp = TcpStream::connect_named(&config)
         .await
-        .map_err(|e| anyhow::anyhow!("{} {}", SQL_TCP_ERROR_TAG, e))?;
+        .map_err(|e| MsSqlError::Transport(e.to_string()))?;
+
+    apply_encryption(&mut config, encryption)?;
 
     // And from here on continue the connection process in a normal way.
-    let s = Client::connect(config, tcp.compat_write())
-        .await
-        .map_err(|e| anyhow::anyhow!("{} {}", SQL_LOGIN_ERROR_TAG, e))?;
+    let s = Client::connect(config, tcp.compat_write()).await?;
     Ok(s)
 }
 
@@ -216,23 +393,30 @@ pub async fn create_client_for_instance(
 ///
 /// * `host` - Hostname of MS SQL server
 /// * `port` - Port of MS SQL server
+/// * `encryption` - TLS mode and, for `Strict`, how to validate the server cert
 #[cfg(windows)]
 pub async fn create_client_for_logged_user(
     host: &str,
     port: u16,
     instance_name: Option<String>,
-) -> Result<Client<Compat<TcpStream>>> {
+    encryption: &EncryptionConfig,
+) -> Result<Client<Compat<TcpStream>>, MsSqlError> {
     let mut config = Config::new();
 
     config.host(host);
     config.port(port);
     config.authentication(AuthMethod::Integrated);
-    config.trust_cert(); // on production, it is not a good idea to do this
     if let Some(name) = instance_name {
         config.instance_name(name);
     }
 
-    let tcp = TcpStream::connect(config.get_addr()).await?;
+    // Resolve the real connect address before `apply_encryption`, which in
+    // `Strict` mode may repoint `config`'s host at `server_hostname` for TLS
+    // verification -- the actual TCP connect must still go to `host`.
+    let addr = config.get_addr();
+    apply_encryption(&mut config, encryption)?;
+
+    let tcp = TcpStream::connect(addr).await?;
     tcp.set_nodelay(true)?;
 
     // To be able to use Tokio's tcp, we're using the `compat_write` from
@@ -245,15 +429,16 @@ pub async fn create_client_for_logged_user(
 pub async fn create_client_for_logged_user(
     _host: &str,
     _port: u16,
-) -> Result<Client<Compat<TcpStream>>> {
-    anyhow::bail!("not supported");
+    _encryption: &EncryptionConfig,
+) -> Result<Client<Compat<TcpStream>>, MsSqlError> {
+    Err(MsSqlError::Other("not supported".to_string()))
 }
 
 /// return Vec<Vec<Row>> as a Results Vec: one Vec<Row> per one statement in query.
 pub async fn run_query(
     client: &mut Client<Compat<TcpStream>>,
     query: &str,
-) -> Result<Vec<Vec<Row>>> {
+) -> Result<Vec<Vec<Row>>, MsSqlError> {
     let stream = Query::new(query).query(client).await?;
     let rows: Vec<Vec<Row>> = stream.into_results().await?;
     Ok(rows)